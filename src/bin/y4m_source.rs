@@ -1,11 +1,14 @@
 use std::io::BufReader;
 use std::io::Read;
+use std::io::{BufRead, Seek, SeekFrom};
+use std::path::PathBuf;
 use imgref::ImgVec;
 use gifski::Collector;
 use y4m::{Colorspace, Decoder, ParseError};
 use yuv::{
-    yuv400_to_rgba, yuv420_to_rgba, yuv422_to_rgba, yuv444_to_rgba, YuvGrayImage, YuvPlanarImage,
-    YuvRange, YuvStandardMatrix,
+    i010_to_rgba, i012_to_rgba, i210_to_rgba, i212_to_rgba, i410_to_rgba, i412_to_rgba,
+    yuv400_to_rgba, yuv420_to_rgba, yuv422_to_rgba, yuv444_to_rgba,
+    YuvGrayImage, YuvPlanarImage, YuvRange, YuvStandardMatrix,
 };
 use crate::{SrcPath, BinResult};
 use crate::source::{Fps, Source, DEFAULT_FPS};
@@ -15,14 +18,28 @@ pub struct Y4MDecoder {
     in_color_space: Option<YuvStandardMatrix>,
     decoder: Decoder<Box<BufReader<dyn Read>>>,
     file_size: Option<u64>,
+    correct_par: bool,
+    /// Path to the source file, kept around for seeking. `None` for stdin,
+    /// which isn't seekable.
+    src_path: Option<PathBuf>,
+    /// Byte offset of each frame's `FRAME` header line, built once by
+    /// [`index_frame_offsets`]. `None` for stdin.
+    frame_offsets: Option<Vec<u64>>,
+    /// Count of GIF frames the whole file yields, accounting for any
+    /// per-`FRAME` interlacing overrides, built once by
+    /// [`index_frame_offsets`] alongside `frame_offsets`. Frames that
+    /// [`Self::collect`] drops to reach the wanted fps are not subtracted.
+    /// `None` for stdin.
+    emitted_frame_count: Option<u64>,
 }
 
 impl Y4MDecoder {
     pub fn new(src: SrcPath, fps: Fps, in_color_space: Option<YuvStandardMatrix>) -> BinResult<Self> {
         let mut file_size = None;
+        let mut src_path = None;
         let reader = match src {
             SrcPath::Path(path) => {
-                let f = std::fs::File::open(path)?;
+                let f = std::fs::File::open(&path)?;
                 let m = f.metadata()?;
                 #[cfg(unix)] {
                     use std::os::unix::fs::MetadataExt;
@@ -32,28 +49,63 @@ impl Y4MDecoder {
                     use std::os::windows::fs::MetadataExt;
                     file_size = Some(m.file_size());
                 }
+                src_path = Some(path);
                 Box::new(BufReader::new(f)) as Box<BufReader<dyn Read>>
             },
             SrcPath::Stdin(buf) => Box::new(buf) as Box<BufReader<dyn Read>>,
         };
 
-        Ok(Self {
-            file_size,
-            fps,
-            in_color_space,
-            decoder: Decoder::new(reader).map_err(|e| match e {
-                y4m::Error::EOF => "The y4m file is truncated or invalid",
-                y4m::Error::BadInput => "The y4m file contains invalid metadata",
-                y4m::Error::UnknownColorspace => "y4m uses an unusual color format that is not supported",
-                y4m::Error::OutOfMemory => "Out of memory, or the y4m file has bogus dimensions",
-                y4m::Error::ParseError(ParseError::InvalidY4M) => "The input is not a y4m file",
-                y4m::Error::ParseError(error) => return format!("y4m contains invalid data: {error}"),
-                y4m::Error::IoError(error) => return format!("I/O error when reading a y4m file: {error}"),
-            }.to_string())?,
-        })
+        let decoder = Decoder::new(reader).map_err(|e| match e {
+            y4m::Error::EOF => "The y4m file is truncated or invalid",
+            y4m::Error::BadInput => "The y4m file contains invalid metadata",
+            y4m::Error::UnknownColorspace => "y4m uses an unusual color format that is not supported",
+            y4m::Error::OutOfMemory => "Out of memory, or the y4m file has bogus dimensions",
+            y4m::Error::ParseError(ParseError::InvalidY4M) => "The input is not a y4m file",
+            y4m::Error::ParseError(error) => return format!("y4m contains invalid data: {error}"),
+            y4m::Error::IoError(error) => return format!("I/O error when reading a y4m file: {error}"),
+        }.to_string())?;
+
+        // Stdin can't be seeked or sized, so it gets no index and no frame
+        // count; seekable files get an exact, O(1)-seekable index plus a
+        // per-frame emitted-frame count (see `emitted_frame_count`).
+        let (frame_offsets, emitted_frame_count) = match src_path.as_deref().and_then(|path| index_frame_offsets(path, &decoder).ok()) {
+            Some((offsets, count)) => (Some(offsets), Some(count)),
+            None => (None, None),
+        };
+
+        Ok(Self { file_size, fps, in_color_space, correct_par: true, src_path, frame_offsets, emitted_frame_count, decoder })
+    }
+
+    /// Jumps the decoder to frame `n` without decoding the frames before it.
+    /// Only available for seekable (non-stdin) sources whose frames were
+    /// indexed successfully in [`Y4MDecoder::new`].
+    pub fn seek_to_frame(&mut self, n: u64) -> BinResult<()> {
+        let path = self.src_path.as_deref().ok_or("Seeking requires a seekable Y4M file, not stdin")?;
+        let &offset = self.frame_offsets.as_ref()
+            .and_then(|offsets| offsets.get(n as usize))
+            .ok_or_else(|| format!("Frame {n} is out of range for this Y4M file"))?;
+
+        let mut f = std::fs::File::open(path)?;
+        f.seek(SeekFrom::Start(offset))?;
+        // The y4m decoder only knows how to parse frames starting right
+        // after a stream header, so re-supply the original header in front
+        // of the file positioned at the target frame's `FRAME` line.
+        let header = read_header_bytes(path)?;
+        let resumed = std::io::Cursor::new(header).chain(f);
+        self.decoder = Decoder::new(Box::new(BufReader::new(resumed)) as Box<BufReader<dyn Read>>)
+            .map_err(|e| format!("Could not resume Y4M decoding at frame {n}: {e}"))?;
+        Ok(())
+    }
+
+    /// Enables or disables rescaling decoded frames to account for a
+    /// non-square sample aspect ratio (the y4m `A` tag). Enabled by default;
+    /// turn it off if the caller already resizes frames downstream.
+    pub fn set_correct_pixel_aspect_ratio(&mut self, enabled: bool) {
+        self.correct_par = enabled;
     }
 }
 
+#[derive(Copy, Clone)]
 enum Samp {
     Mono,
     S1x1,
@@ -61,8 +113,249 @@ enum Samp {
     S2x2,
 }
 
+/// Parses the `COLORRANGE=` token out of either the stream header or a
+/// per-`FRAME` override. `None` if the token is absent.
+fn parse_range_token(params: &str) -> Option<YuvRange> {
+    params.split_once("COLORRANGE=").map(|(_, r)| {
+        if r.starts_with("FULL") { YuvRange::Full } else { YuvRange::Limited }
+    })
+}
+
+/// Byte length of the Y plane and of each of the U/V planes for a frame of
+/// the given geometry, subsampling and bit depth.
+fn expected_plane_lens(width: usize, height: usize, samp: Samp, bytes_per_sample: usize) -> (usize, usize) {
+    let (cw, ch) = match samp {
+        Samp::Mono => (0, 0),
+        Samp::S1x1 => (width, height),
+        Samp::S2x1 => (width.div_ceil(2), height),
+        Samp::S2x2 => (width.div_ceil(2), height.div_ceil(2)),
+    };
+    (width * height * bytes_per_sample, cw * ch * bytes_per_sample)
+}
+
+/// Extracts the colorspace tag (`C420`, `C444p10`, `Cmono12`, ...) exactly as
+/// it appears in a y4m params string, stream-level or per-`FRAME`. Unlike a
+/// `starts_with('C')` check, this can't be confused with the unrelated
+/// `COLORRANGE=` token, which also starts with `C`.
+fn colorspace_tag(params: &str) -> Option<&str> {
+    const TAGS: &[&str] = &[
+        "Cmono", "Cmono12",
+        "C420", "C420jpeg", "C420paldv", "C420mpeg2", "C420p10", "C420p12",
+        "C422", "C422p10", "C422p12",
+        "C444", "C444p10", "C444p12",
+    ];
+    params.split_ascii_whitespace().find(|tok| TAGS.contains(tok))
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum Interlacing {
+    Progressive,
+    TopFieldFirst,
+    BottomFieldFirst,
+    Mixed,
+}
+
+/// Parses the `I` tag (`Ip`/`It`/`Ib`/`Im`) out of either the stream header
+/// or a per-`FRAME` override. `None` if the tag is absent; an unrecognised
+/// value is treated as progressive.
+fn parse_interlacing_token(params: &str) -> Option<Interlacing> {
+    params.split_ascii_whitespace()
+        .find_map(|tok| tok.strip_prefix('I'))
+        .map(|mode| match mode {
+            "t" => Interlacing::TopFieldFirst,
+            "b" => Interlacing::BottomFieldFirst,
+            "m" => Interlacing::Mixed,
+            _ => Interlacing::Progressive,
+        })
+}
+
+/// Like [`parse_interlacing_token`], but defaults to progressive for the
+/// stream-level header where the tag is conventionally always present.
+fn parse_interlacing(raw_params: &str) -> Interlacing {
+    parse_interlacing_token(raw_params).unwrap_or(Interlacing::Progressive)
+}
+
+/// Bob-deinterlaces one field (even or odd rows) of a combed RGBA frame into
+/// a full-height progressive frame, interpolating the missing lines from
+/// their two neighbouring present lines (edge lines are duplicated).
+fn bob_field(full: &[rgb::RGBA8], width: usize, height: usize, keep_even_rows: bool) -> Vec<rgb::RGBA8> {
+    let mut out = vec![rgb::RGBA8::new(0, 0, 0, 0); width * height];
+    for row in 0..height {
+        let dst = &mut out[row * width..(row + 1) * width];
+        if (row % 2 == 0) == keep_even_rows {
+            dst.copy_from_slice(&full[row * width..(row + 1) * width]);
+        } else {
+            let prev = row.checked_sub(1);
+            let next = (row + 1 < height).then_some(row + 1);
+            for (col, px) in dst.iter_mut().enumerate() {
+                *px = match (prev, next) {
+                    (Some(p), Some(n)) => blend_rgba(full[p * width + col], full[n * width + col]),
+                    (Some(p), None) => full[p * width + col],
+                    (None, Some(n)) => full[n * width + col],
+                    (None, None) => full[col],
+                };
+            }
+        }
+    }
+    out
+}
+
+fn blend_rgba(a: rgb::RGBA8, b: rgb::RGBA8) -> rgb::RGBA8 {
+    rgb::RGBA8::new(
+        ((u16::from(a.r) + u16::from(b.r)) / 2) as u8,
+        ((u16::from(a.g) + u16::from(b.g)) / 2) as u8,
+        ((u16::from(a.b) + u16::from(b.b)) / 2) as u8,
+        ((u16::from(a.a) + u16::from(b.a)) / 2) as u8,
+    )
+}
+
+/// Parses the `A` stream tag (`An:d`, sample aspect ratio) out of the raw
+/// y4m header parameters. `0:0` (unknown) and a missing tag both mean 1:1.
+fn parse_pixel_aspect_ratio(raw_params: &str) -> (u32, u32) {
+    raw_params.split_ascii_whitespace()
+        .find_map(|tok| tok.strip_prefix('A'))
+        .and_then(|ar| ar.split_once(':'))
+        .and_then(|(n, d)| Some((n.parse::<u32>().ok()?, d.parse::<u32>().ok()?)))
+        .filter(|&(n, d)| n != 0 && d != 0)
+        .unwrap_or((1, 1))
+}
+
+/// Rescales a frame from its stored pixel grid to its display dimensions so
+/// that a non-square sample aspect ratio renders correctly.
+fn rescale_for_par(img: ImgVec<rgb::RGBA8>, par_num: u32, par_den: u32) -> BinResult<ImgVec<rgb::RGBA8>> {
+    let (width, height) = (img.width(), img.height());
+    let (new_width, new_height) = if par_num > par_den {
+        let den = u64::from(par_den);
+        (((width as u64 * u64::from(par_num) + den / 2) / den) as usize, height)
+    } else {
+        let den = u64::from(par_num);
+        (width, ((height as u64 * u64::from(par_den) + den / 2) / den) as usize)
+    };
+    if new_width == width && new_height == height {
+        return Ok(img);
+    }
+    let mut resizer = resize::new(width, height, new_width, new_height, resize::Pixel::RGBA8, resize::Type::Triangle)
+        .map_err(|e| format!("Could not rescale frame for pixel aspect ratio: {e}"))?;
+    let mut dst = vec![rgb::RGBA8::new(0, 0, 0, 0); new_width * new_height];
+    resizer.resize(img.buf(), &mut dst)
+        .map_err(|e| format!("Could not rescale frame for pixel aspect ratio: {e}"))?;
+    Ok(ImgVec::new(dst, new_width, new_height))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn emit_frame(
+    c: &mut Collector, idx: &mut u64, pixels: ImgVec<rgb::RGBA8>, pts: f64,
+    par: (u32, u32), correct_par: bool,
+) -> BinResult<()> {
+    let pixels = if correct_par && par != (1, 1) { rescale_for_par(pixels, par.0, par.1)? } else { pixels };
+    c.add_frame_rgba(*idx, pixels, pts)?;
+    *idx += 1;
+    Ok(())
+}
+
+/// Converts a byte plane from a high-bit-depth y4m stream (2 bytes per
+/// sample, little-endian, as mandated by the y4m spec) into its `u16` samples.
+fn plane_to_u16(plane: &[u8]) -> Vec<u16> {
+    plane.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]])).collect()
+}
+
+/// Down-shifts a `depth`-bit plane to 8-bit, rounding to nearest instead of
+/// truncating. `yuv` has no direct n-bit-grayscale-to-RGBA8 path, so Cmono12
+/// is handled by shifting the Y plane down first and reusing the 8-bit path.
+fn downshift_plane(plane: &[u16], depth: u8) -> Vec<u8> {
+    let max = (1u32 << depth) - 1;
+    let half = max / 2;
+    plane.iter().map(|&v| ((u32::from(v) * 255 + half) / max) as u8).collect()
+}
+
+/// Reads the y4m stream header (`YUV4MPEG2 ...\n`) verbatim, for re-use when
+/// resuming decoding from a byte offset found via [`index_frame_offsets`].
+fn read_header_bytes(path: &std::path::Path) -> std::io::Result<Vec<u8>> {
+    let mut f = BufReader::new(std::fs::File::open(path)?);
+    let mut header = Vec::new();
+    f.read_until(b'\n', &mut header)?;
+    Ok(header)
+}
+
+/// Size in bytes of one frame's Y/U/V planes for the stream's geometry,
+/// colorspace and sample width, not counting its `FRAME` header line.
+fn frame_plane_bytes(decoder: &Decoder<Box<BufReader<dyn Read>>>) -> u64 {
+    let w = decoder.get_width() as u64;
+    let h = decoder.get_height() as u64;
+    let bps = decoder.get_bytes_per_sample() as u64;
+    let (cw, ch) = match decoder.get_colorspace() {
+        Colorspace::Cmono | Colorspace::Cmono12 => (0, 0),
+        Colorspace::C420 | Colorspace::C420p10 | Colorspace::C420p12
+        | Colorspace::C420jpeg | Colorspace::C420paldv | Colorspace::C420mpeg2 => (w.div_ceil(2), h.div_ceil(2)),
+        Colorspace::C422 | Colorspace::C422p10 | Colorspace::C422p12 => (w.div_ceil(2), h),
+        _ => (w, h), // C444 and anything else defaults to full-resolution chroma
+    };
+    (w * h + 2 * cw * ch) * bps
+}
+
+/// Builds an exact, seekable frame index by walking the file once: skip the
+/// stream header, then repeatedly skip a `FRAME\n`-terminated line plus the
+/// frame's plane bytes, recording the byte offset of each `FRAME` line.
+/// Also tallies the number of GIF frames the file yields (two per It/Ib
+/// frame), since a per-`FRAME` interlacing override (falling back to the
+/// stream's own tag when absent, exactly as `collect` does) can make that
+/// differ from the raw source frame count. Frames that `collect` drops to
+/// reach the wanted fps are not subtracted.
+fn index_frame_offsets(path: &std::path::Path, decoder: &Decoder<Box<BufReader<dyn Read>>>) -> std::io::Result<(Vec<u64>, u64)> {
+    let header = read_header_bytes(path)?;
+    let header_len = header.len() as u64;
+    let stream_interlacing = parse_interlacing(&String::from_utf8_lossy(&header));
+    let frame_bytes = frame_plane_bytes(decoder);
+
+    let mut f = BufReader::new(std::fs::File::open(path)?);
+    f.seek(SeekFrom::Start(header_len))?;
+
+    let mut offsets = Vec::new();
+    let mut emitted = 0u64;
+    let mut line = Vec::new();
+    loop {
+        let frame_start = f.stream_position()?;
+        line.clear();
+        if f.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        if !line.starts_with(b"FRAME") {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "expected a FRAME header"));
+        }
+        offsets.push(frame_start);
+        let frame_params = String::from_utf8_lossy(&line[b"FRAME".len()..]).into_owned();
+        let interlacing = parse_interlacing_token(frame_params.trim()).unwrap_or(stream_interlacing);
+        emitted += match interlacing {
+            Interlacing::TopFieldFirst | Interlacing::BottomFieldFirst => 2,
+            Interlacing::Progressive | Interlacing::Mixed => 1,
+        };
+        f.seek(SeekFrom::Current(frame_bytes as i64))?;
+    }
+    Ok((offsets, emitted))
+}
+
 impl Source for Y4MDecoder {
     fn total_frames(&self) -> Option<u64> {
+        // Bob-deinterlacing emits two GIF frames per source frame for
+        // It/Ib streams, so callers using this for progress/capacity need
+        // the doubled count, not the raw source frame count. For seekable
+        // files, `emitted_frame_count` was tallied frame-by-frame alongside
+        // the index, so it already accounts for any per-FRAME interlacing
+        // override exactly as `collect` applies them (fps decimation is not
+        // subtracted).
+        if let Some(count) = self.emitted_frame_count {
+            return Some(count);
+        }
+
+        // A file whose index pass failed falls back to a size-based
+        // estimate using only the stream-level interlacing tag, so per-FRAME
+        // interlacing overrides that disagree with it make the count inexact.
+        // Stdin has no file size, so it gets `None` here.
+        let raw_params_str = String::from_utf8_lossy(self.decoder.get_raw_params()).into_owned();
+        let emitted_per_frame = match parse_interlacing(&raw_params_str) {
+            Interlacing::TopFieldFirst | Interlacing::BottomFieldFirst => 2,
+            Interlacing::Progressive | Interlacing::Mixed => 1,
+        };
         self.file_size.map(|file_size| {
             let w = self.decoder.get_width();
             let h = self.decoder.get_height();
@@ -84,7 +377,7 @@ impl Source for Y4MDecoder {
                 Colorspace::C444p12 => 12,
                 _ => 12,
             };
-            file_size.saturating_sub(self.decoder.get_raw_params().len() as _) / (w * h * d * s / 4 + 6) as u64
+            file_size.saturating_sub(self.decoder.get_raw_params().len() as _) / (w * h * d * s / 4 + 6) as u64 * emitted_per_frame
         })
     }
 
@@ -96,30 +389,31 @@ impl Source for Y4MDecoder {
         let width = self.decoder.get_width();
         let height = self.decoder.get_height();
         let raw_params_str = &*String::from_utf8_lossy(self.decoder.get_raw_params()).into_owned();
-        let range = raw_params_str.split_once("COLORRANGE=").map(|(_, r)| {
-            if r.starts_with("FULL") { YuvRange::Full } else { YuvRange::Limited }
-        });
 
         let matrix = self.in_color_space.unwrap_or({
             if height <= 480 && width <= 720 { YuvStandardMatrix::Bt601 } else { YuvStandardMatrix::Bt709 }
         });
-        let range = range.unwrap_or(YuvRange::Limited);
-
-        let samp = match self.decoder.get_colorspace() {
-            Colorspace::Cmono => Samp::Mono,
-            Colorspace::Cmono12 => return Err("Y4M with Cmono12 is not supported yet".into()),
-            Colorspace::C420 => Samp::S2x2,
-            Colorspace::C420p10 => return Err("Y4M with C420p10 is not supported yet".into()),
-            Colorspace::C420p12 => return Err("Y4M with C420p12 is not supported yet".into()),
-            Colorspace::C420jpeg => Samp::S2x2,
-            Colorspace::C420paldv => Samp::S2x2,
-            Colorspace::C420mpeg2 => Samp::S2x2,
-            Colorspace::C422 => Samp::S2x1,
-            Colorspace::C422p10 => return Err("Y4M with C422p10 is not supported yet".into()),
-            Colorspace::C422p12 => return Err("Y4M with C422p12 is not supported yet".into()),
-            Colorspace::C444 => Samp::S1x1,
-            Colorspace::C444p10 => return Err("Y4M with C444p10 is not supported yet".into()),
-            Colorspace::C444p12 => return Err("Y4M with C444p12 is not supported yet".into()),
+        let stream_range = parse_range_token(raw_params_str).unwrap_or(YuvRange::Limited);
+        let stream_interlacing = parse_interlacing(raw_params_str);
+        let stream_colorspace_tag = colorspace_tag(raw_params_str);
+        let mut warned_mixed = false;
+        let par = parse_pixel_aspect_ratio(raw_params_str);
+
+        let stream_samp_depth = match self.decoder.get_colorspace() {
+            Colorspace::Cmono => (Samp::Mono, 8),
+            Colorspace::Cmono12 => (Samp::Mono, 12),
+            Colorspace::C420 => (Samp::S2x2, 8),
+            Colorspace::C420p10 => (Samp::S2x2, 10),
+            Colorspace::C420p12 => (Samp::S2x2, 12),
+            Colorspace::C420jpeg => (Samp::S2x2, 8),
+            Colorspace::C420paldv => (Samp::S2x2, 8),
+            Colorspace::C420mpeg2 => (Samp::S2x2, 8),
+            Colorspace::C422 => (Samp::S2x1, 8),
+            Colorspace::C422p10 => (Samp::S2x1, 10),
+            Colorspace::C422p12 => (Samp::S2x1, 12),
+            Colorspace::C444 => (Samp::S1x1, 8),
+            Colorspace::C444p10 => (Samp::S1x1, 10),
+            Colorspace::C444p12 => (Samp::S1x1, 12),
             _ => return Err(format!("Y4M uses unsupported color mode {raw_params_str}").into()),
         };
         if width == 0 || width > u16::MAX as _ || height == 0 || height > u16::MAX as _ {
@@ -144,71 +438,183 @@ impl Source for Y4MDecoder {
                     }
                     wanted_pts += wanted_frame_time;
 
+                    // A FRAME line may carry its own tokens that override the
+                    // stream-level range and interlacing for this one frame
+                    // (e.g. from muxers that splice mixed sources). The decoder
+                    // always cuts Y/U/V out of a fixed-size buffer computed
+                    // from the stream-level colorspace, so that part can't be
+                    // overridden per frame — only range and interlacing, which
+                    // don't affect how the planes are sized, are re-derived here.
+                    let frame_params_owned = frame.get_raw_params()
+                        .map(|p| String::from_utf8_lossy(p).into_owned())
+                        .unwrap_or_default();
+                    let frame_params = frame_params_owned.trim();
+                    let (samp, depth) = stream_samp_depth;
+                    let range = parse_range_token(frame_params).unwrap_or(stream_range);
+                    let interlacing = parse_interlacing_token(frame_params).unwrap_or(stream_interlacing);
+                    let bytes_per_sample = if depth > 8 { 2 } else { 1 };
+
+                    // A true colorspace change mid-stream isn't something
+                    // `y4m::Decoder` can even hand us: plane sizes are fixed
+                    // from the stream-level header at construction time, so a
+                    // spliced-in frame of a different geometry surfaces as a
+                    // decoder parse error, not as a differently-shaped frame
+                    // here. What a muxer *can* still do is annotate one FRAME
+                    // line with a `C...` tag that disagrees with the header
+                    // while supplying planes still sized for the header's
+                    // colorspace — catch that mismatch explicitly instead of
+                    // silently decoding it as the wrong format.
+                    if let Some(frame_tag) = colorspace_tag(frame_params) {
+                        if Some(frame_tag) != stream_colorspace_tag {
+                            return Err(format!(
+                                "Y4M FRAME override changes colorspace mid-stream ({frame_tag}), which gifski can't represent in one GIF"
+                            ).into());
+                        }
+                    }
+
                     let y = frame.get_y_plane();
                     if y.is_empty() {
                         return bad_frame(raw_params_str);
                     }
                     let u = frame.get_u_plane();
                     let v = frame.get_v_plane();
+                    let (expected_y_len, expected_uv_len) = expected_plane_lens(width, height, samp, bytes_per_sample);
+                    debug_assert_eq!(y.len(), expected_y_len, "y4m always sizes planes from the stream-level colorspace");
+                    debug_assert_eq!(u.len(), expected_uv_len, "y4m always sizes planes from the stream-level colorspace");
+                    debug_assert_eq!(v.len(), expected_uv_len, "y4m always sizes planes from the stream-level colorspace");
                     let width_u32 = width as u32;
                     let height_u32 = height as u32;
-                    let mut rgba = vec![0; width * height * 4];
-
-                    let res = match samp {
-                        Samp::Mono => {
-                            let img = YuvGrayImage {
-                                y_plane: y,
-                                y_stride: width_u32,
-                                width: width_u32,
-                                height: height_u32,
-                            };
-                            yuv400_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
-                        },
-                        Samp::S1x1 => {
-                            let img = YuvPlanarImage {
-                                y_plane: y,
-                                y_stride: width_u32,
-                                u_plane: u,
-                                u_stride: width_u32,
-                                v_plane: v,
-                                v_stride: width_u32,
-                                width: width_u32,
-                                height: height_u32,
-                            };
-                            yuv444_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
-                        },
-                        Samp::S2x1 => {
-                            let uv_stride = width_u32.div_ceil(2);
-                            let img = YuvPlanarImage {
-                                y_plane: y,
-                                y_stride: width_u32,
-                                u_plane: u,
-                                u_stride: uv_stride,
-                                v_plane: v,
-                                v_stride: uv_stride,
-                                width: width_u32,
-                                height: height_u32,
-                            };
-                            yuv422_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
-                        },
-                        Samp::S2x2 => {
-                            let uv_stride = width_u32.div_ceil(2);
-                            let img = YuvPlanarImage {
-                                y_plane: y,
-                                y_stride: width_u32,
-                                u_plane: u,
-                                u_stride: uv_stride,
-                                v_plane: v,
-                                v_stride: uv_stride,
-                                width: width_u32,
-                                height: height_u32,
-                            };
-                            yuv420_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
-                        },
+
+                    let rgba = if bytes_per_sample == 2 {
+                        let y16 = plane_to_u16(y);
+                        let mut rgba = vec![0u8; width * height * 4];
+                        let res = match samp {
+                            Samp::Mono => {
+                                // yuv has no n-bit grayscale-to-RGBA8 entry point,
+                                // so shift the Y plane to 8-bit and reuse yuv400.
+                                let y8 = downshift_plane(&y16, depth);
+                                let img = YuvGrayImage {
+                                    y_plane: &y8,
+                                    y_stride: width_u32,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                yuv400_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
+                            },
+                            Samp::S1x1 => {
+                                let u16_ = plane_to_u16(u);
+                                let v16 = plane_to_u16(v);
+                                let img = YuvPlanarImage {
+                                    y_plane: &y16,
+                                    y_stride: width_u32,
+                                    u_plane: &u16_,
+                                    u_stride: width_u32,
+                                    v_plane: &v16,
+                                    v_stride: width_u32,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                if depth == 12 { i412_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix) }
+                                else { i410_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix) }
+                            },
+                            Samp::S2x1 => {
+                                let uv_stride = width_u32.div_ceil(2);
+                                let u16_ = plane_to_u16(u);
+                                let v16 = plane_to_u16(v);
+                                let img = YuvPlanarImage {
+                                    y_plane: &y16,
+                                    y_stride: width_u32,
+                                    u_plane: &u16_,
+                                    u_stride: uv_stride,
+                                    v_plane: &v16,
+                                    v_stride: uv_stride,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                if depth == 12 { i212_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix) }
+                                else { i210_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix) }
+                            },
+                            Samp::S2x2 => {
+                                let uv_stride = width_u32.div_ceil(2);
+                                let u16_ = plane_to_u16(u);
+                                let v16 = plane_to_u16(v);
+                                let img = YuvPlanarImage {
+                                    y_plane: &y16,
+                                    y_stride: width_u32,
+                                    u_plane: &u16_,
+                                    u_stride: uv_stride,
+                                    v_plane: &v16,
+                                    v_stride: uv_stride,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                if depth == 12 { i012_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix) }
+                                else { i010_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix) }
+                            },
+                        };
+                        if let Err(err) = res {
+                            return Err(format!("Bad Y4M frame (using {raw_params_str}): {err}").into());
+                        }
+                        rgba
+                    } else {
+                        let mut rgba = vec![0; width * height * 4];
+                        let res = match samp {
+                            Samp::Mono => {
+                                let img = YuvGrayImage {
+                                    y_plane: y,
+                                    y_stride: width_u32,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                yuv400_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
+                            },
+                            Samp::S1x1 => {
+                                let img = YuvPlanarImage {
+                                    y_plane: y,
+                                    y_stride: width_u32,
+                                    u_plane: u,
+                                    u_stride: width_u32,
+                                    v_plane: v,
+                                    v_stride: width_u32,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                yuv444_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
+                            },
+                            Samp::S2x1 => {
+                                let uv_stride = width_u32.div_ceil(2);
+                                let img = YuvPlanarImage {
+                                    y_plane: y,
+                                    y_stride: width_u32,
+                                    u_plane: u,
+                                    u_stride: uv_stride,
+                                    v_plane: v,
+                                    v_stride: uv_stride,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                yuv422_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
+                            },
+                            Samp::S2x2 => {
+                                let uv_stride = width_u32.div_ceil(2);
+                                let img = YuvPlanarImage {
+                                    y_plane: y,
+                                    y_stride: width_u32,
+                                    u_plane: u,
+                                    u_stride: uv_stride,
+                                    v_plane: v,
+                                    v_stride: uv_stride,
+                                    width: width_u32,
+                                    height: height_u32,
+                                };
+                                yuv420_to_rgba(&img, &mut rgba, width_u32 * 4, range, matrix)
+                            },
+                        };
+                        if let Err(err) = res {
+                            return Err(format!("Bad Y4M frame (using {raw_params_str}): {err}").into());
+                        }
+                        rgba
                     };
-                    if let Err(err) = res {
-                        return Err(format!("Bad Y4M frame (using {raw_params_str}): {err}").into());
-                    }
 
                     let mut out = Vec::with_capacity(width * height);
                     for px in rgba.chunks_exact(4) {
@@ -217,10 +623,25 @@ impl Source for Y4MDecoder {
                     if out.len() != width * height {
                         return bad_frame(raw_params_str);
                     }
-                    let pixels = ImgVec::new(out, width, height);
 
-                    c.add_frame_rgba(idx, pixels, this_frame_pts)?;
-                    idx += 1;
+                    match interlacing {
+                        Interlacing::TopFieldFirst | Interlacing::BottomFieldFirst => {
+                            let top_first = interlacing == Interlacing::TopFieldFirst;
+                            let half_step = frame_time / 2. / f64::from(self.fps.speed);
+                            let first = bob_field(&out, width, height, top_first);
+                            let second = bob_field(&out, width, height, !top_first);
+                            emit_frame(c, &mut idx, ImgVec::new(first, width, height), this_frame_pts, par, self.correct_par)?;
+                            emit_frame(c, &mut idx, ImgVec::new(second, width, height), this_frame_pts + half_step, par, self.correct_par)?;
+                        },
+                        Interlacing::Mixed | Interlacing::Progressive => {
+                            if interlacing == Interlacing::Mixed && !warned_mixed {
+                                eprintln!("Y4M stream is marked as mixed interlacing (Im); treating frames as progressive");
+                                warned_mixed = true;
+                            }
+                            let pixels = ImgVec::new(out, width, height);
+                            emit_frame(c, &mut idx, pixels, this_frame_pts, par, self.correct_par)?;
+                        },
+                    }
                 },
                 Err(y4m::Error::EOF) => break,
                 Err(e) => return Err(e.into()),
@@ -229,3 +650,178 @@ impl Source for Y4MDecoder {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plane_to_u16_reads_little_endian_pairs() {
+        assert_eq!(plane_to_u16(&[0x00, 0x01, 0xff, 0x03]), vec![0x0100, 0x03ff]);
+    }
+
+    #[test]
+    fn downshift_plane_maps_full_range_10_bit() {
+        let shifted = downshift_plane(&[0, 511, 1023], 10);
+        assert_eq!(shifted, vec![0, 127, 255]);
+    }
+
+    #[test]
+    fn downshift_plane_maps_full_range_12_bit() {
+        let shifted = downshift_plane(&[0, 2047, 4095], 12);
+        assert_eq!(shifted, vec![0, 127, 255]);
+    }
+
+    #[test]
+    fn parse_interlacing_token_recognises_all_tags() {
+        assert_eq!(parse_interlacing_token("W10 H10 It A1:1"), Some(Interlacing::TopFieldFirst));
+        assert_eq!(parse_interlacing_token("Ib"), Some(Interlacing::BottomFieldFirst));
+        assert_eq!(parse_interlacing_token("Im"), Some(Interlacing::Mixed));
+        assert_eq!(parse_interlacing_token("Ip"), Some(Interlacing::Progressive));
+    }
+
+    #[test]
+    fn parse_interlacing_token_absent_is_none() {
+        assert_eq!(parse_interlacing_token("W10 H10 C420"), None);
+    }
+
+    #[test]
+    fn parse_interlacing_defaults_to_progressive() {
+        assert_eq!(parse_interlacing("W10 H10 C420"), Interlacing::Progressive);
+    }
+
+    fn px(v: u8) -> rgb::RGBA8 {
+        rgb::RGBA8::new(v, v, v, 255)
+    }
+
+    #[test]
+    fn bob_field_keeps_its_own_rows_and_interpolates_the_rest() {
+        // 4 rows of a 1px-wide frame, values 0/10/20/30 top to bottom.
+        let full: Vec<rgb::RGBA8> = [0, 10, 20, 30].iter().map(|&v| px(v)).collect();
+        let even = bob_field(&full, 1, 4, true);
+        assert_eq!(even[0], px(0)); // kept
+        assert_eq!(even[1], px(10)); // interpolated from rows 0 and 2 (0 and 20) -> 10
+        assert_eq!(even[2], px(20)); // kept
+        assert_eq!(even[3], px(20)); // edge: duplicated from row 2 (no row 4)
+    }
+
+    #[test]
+    fn bob_field_duplicates_the_first_row_at_the_top_edge() {
+        let full: Vec<rgb::RGBA8> = [0, 10, 20, 30].iter().map(|&v| px(v)).collect();
+        let odd = bob_field(&full, 1, 4, false);
+        assert_eq!(odd[0], px(10)); // edge: duplicated from row 1 (no row -1)
+        assert_eq!(odd[1], px(10)); // kept
+        assert_eq!(odd[3], px(30)); // kept
+    }
+
+    #[test]
+    fn parse_pixel_aspect_ratio_reads_the_a_tag() {
+        assert_eq!(parse_pixel_aspect_ratio("W10 H10 A32:27"), (32, 27));
+    }
+
+    #[test]
+    fn parse_pixel_aspect_ratio_treats_0_0_as_square() {
+        assert_eq!(parse_pixel_aspect_ratio("W10 H10 A0:0"), (1, 1));
+    }
+
+    #[test]
+    fn parse_pixel_aspect_ratio_defaults_to_square_when_absent() {
+        assert_eq!(parse_pixel_aspect_ratio("W10 H10 C420"), (1, 1));
+    }
+
+    #[test]
+    fn expected_plane_lens_mono_has_no_chroma() {
+        assert_eq!(expected_plane_lens(4, 2, Samp::Mono, 1), (8, 0));
+    }
+
+    #[test]
+    fn expected_plane_lens_4_2_0_halves_both_chroma_dimensions_and_rounds_up() {
+        // odd width/height must round the chroma plane size up, per the y4m spec.
+        assert_eq!(expected_plane_lens(5, 3, Samp::S2x2, 1), (15, 3 * 2));
+    }
+
+    #[test]
+    fn expected_plane_lens_4_2_2_halves_only_chroma_width() {
+        assert_eq!(expected_plane_lens(5, 3, Samp::S2x1, 1), (15, 3 * 3));
+    }
+
+    #[test]
+    fn expected_plane_lens_4_4_4_keeps_full_chroma_resolution() {
+        assert_eq!(expected_plane_lens(4, 2, Samp::S1x1, 1), (8, 8));
+    }
+
+    #[test]
+    fn expected_plane_lens_scales_with_bytes_per_sample() {
+        assert_eq!(expected_plane_lens(4, 2, Samp::S1x1, 2), (16, 16));
+    }
+
+    #[test]
+    fn parse_range_token_recognises_full_and_limited() {
+        assert_eq!(parse_range_token("W10 H10 COLORRANGE=FULL"), Some(YuvRange::Full));
+        assert_eq!(parse_range_token("W10 H10 COLORRANGE=LIMITED"), Some(YuvRange::Limited));
+    }
+
+    #[test]
+    fn parse_range_token_absent_is_none() {
+        assert_eq!(parse_range_token("W10 H10 C420"), None);
+    }
+
+    #[test]
+    fn parse_range_token_works_on_a_frame_override() {
+        // Per-FRAME overrides are passed through the same parser as the
+        // stream header, just with a shorter params string.
+        assert_eq!(parse_range_token("COLORRANGE=FULL"), Some(YuvRange::Full));
+    }
+
+    #[test]
+    fn colorspace_tag_finds_the_c_tag() {
+        assert_eq!(colorspace_tag("W10 H10 C420 Ip A1:1"), Some("C420"));
+        assert_eq!(colorspace_tag("C444p10"), Some("C444p10"));
+    }
+
+    #[test]
+    fn colorspace_tag_is_not_confused_with_colorrange() {
+        // COLORRANGE= also starts with 'C', but isn't a colorspace tag.
+        assert_eq!(colorspace_tag("COLORRANGE=FULL"), None);
+    }
+
+    #[test]
+    fn colorspace_tag_absent_is_none() {
+        assert_eq!(colorspace_tag("W10 H10 Ip"), None);
+    }
+
+    // The following cover the precedence rule used in `collect()`: a
+    // per-FRAME override token wins when present, otherwise the stream's
+    // own value applies.
+    #[test]
+    fn frame_override_takes_precedence_over_stream_default() {
+        let stream_range = parse_range_token("COLORRANGE=LIMITED").unwrap_or(YuvRange::Limited);
+        let frame_params = "COLORRANGE=FULL";
+        let effective = parse_range_token(frame_params).unwrap_or(stream_range);
+        assert_eq!(effective, YuvRange::Full);
+    }
+
+    #[test]
+    fn stream_default_applies_when_frame_has_no_override() {
+        let stream_range = parse_range_token("COLORRANGE=FULL").unwrap_or(YuvRange::Limited);
+        let frame_params = ""; // FRAME line with no tokens
+        let effective = parse_range_token(frame_params).unwrap_or(stream_range);
+        assert_eq!(effective, YuvRange::Full);
+    }
+
+    #[test]
+    fn frame_interlacing_override_takes_precedence_over_stream_default() {
+        let stream_interlacing = parse_interlacing("W10 H10 Ip");
+        let frame_params = "It";
+        let effective = parse_interlacing_token(frame_params).unwrap_or(stream_interlacing);
+        assert_eq!(effective, Interlacing::TopFieldFirst);
+    }
+
+    #[test]
+    fn stream_interlacing_applies_when_frame_has_no_override() {
+        let stream_interlacing = parse_interlacing("W10 H10 Ib");
+        let frame_params = "";
+        let effective = parse_interlacing_token(frame_params).unwrap_or(stream_interlacing);
+        assert_eq!(effective, Interlacing::BottomFieldFirst);
+    }
+}